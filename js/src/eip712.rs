@@ -0,0 +1,194 @@
+// Copyright 2023-2024 Futureverse Corporation Limited
+
+//! EIP-712 typed-data hashing, signing and verification for V1 doughnuts, so wallets
+//! like MetaMask can render a structured `Doughnut` message instead of signing the
+//! opaque hex blob `sign_eip191` produces.
+//!
+//! `DoughnutV1` has no `chainId`/`verifyingContract` fields (and, being defined in the
+//! external `doughnut_rs` crate, can't be given any), so both are threaded through as
+//! reserved toppings the same way `delegate` threads its proof -- stripped from the
+//! attenuation check and excluded when toppings are copied forward.
+
+use alloc::{
+    string::{String, ToString},
+    vec::Vec,
+};
+use doughnut_rs::{doughnut::DoughnutV1, traits::DoughnutApi};
+use libsecp256k1::{recover, sign, Message, RecoveryId, Signature};
+use sha3::{Digest, Keccak256};
+
+/// Reserved topping key carrying the `chainId` an EIP-712 signature was produced for.
+pub(crate) const CHAIN_ID_TOPPING_KEY: &str = "_eip712_chainId";
+/// Reserved topping key carrying the `verifyingContract` an EIP-712 signature was produced for.
+pub(crate) const VERIFYING_CONTRACT_TOPPING_KEY: &str = "_eip712_verifyingContract";
+
+fn keccak256(data: &[u8]) -> [u8; 32] {
+    let mut hasher = Keccak256::new();
+    hasher.update(data);
+    hasher.finalize().into()
+}
+
+fn pad_left_32(bytes: &[u8]) -> [u8; 32] {
+    let mut padded = [0u8; 32];
+    let start = 32 - bytes.len();
+    padded[start..].copy_from_slice(bytes);
+    padded
+}
+
+fn domain_type_hash() -> [u8; 32] {
+    keccak256(b"EIP712Domain(string name,string version,uint256 chainId,address verifyingContract)")
+}
+
+fn topping_type_hash() -> [u8; 32] {
+    keccak256(b"Topping(string key,bytes value)")
+}
+
+fn doughnut_type_hash() -> [u8; 32] {
+    keccak256(
+        b"Doughnut(bytes issuer,bytes holder,uint8 feeMode,uint32 expiry,uint32 notBefore,bytes32 toppingsHash)",
+    )
+}
+
+fn hash_topping(key: &str, value: &[u8]) -> [u8; 32] {
+    let mut buf = Vec::with_capacity(32 * 3);
+    buf.extend_from_slice(&topping_type_hash());
+    buf.extend_from_slice(&keccak256(key.as_bytes()));
+    buf.extend_from_slice(&keccak256(value));
+    keccak256(&buf)
+}
+
+fn hash_toppings(toppings: &[(String, Vec<u8>)]) -> [u8; 32] {
+    let mut buf = Vec::with_capacity(32 * toppings.len());
+    for (key, value) in toppings {
+        buf.extend_from_slice(&hash_topping(key, value));
+    }
+    keccak256(&buf)
+}
+
+fn domain_separator(chain_id: u64, verifying_contract: &[u8; 20]) -> [u8; 32] {
+    let mut buf = Vec::with_capacity(32 * 4);
+    buf.extend_from_slice(&domain_type_hash());
+    buf.extend_from_slice(&keccak256(b"Doughnut"));
+    buf.extend_from_slice(&keccak256(b"1"));
+    buf.extend_from_slice(&pad_left_32(&chain_id.to_be_bytes()));
+    buf.extend_from_slice(&pad_left_32(verifying_contract));
+    keccak256(&buf)
+}
+
+/// Toppings as carried for hashing, i.e. excluding the two reserved keys that carry the
+/// domain itself -- they describe the signature's binding, not a granted permission.
+fn hashable_toppings(toppings: &[(String, Vec<u8>)]) -> Vec<(String, Vec<u8>)> {
+    toppings
+        .iter()
+        .filter(|(key, _)| key != CHAIN_ID_TOPPING_KEY && key != VERIFYING_CONTRACT_TOPPING_KEY)
+        .cloned()
+        .collect()
+}
+
+fn hash_struct(doughnut: &DoughnutV1) -> [u8; 32] {
+    let mut buf = Vec::with_capacity(32 * 6);
+    buf.extend_from_slice(&doughnut_type_hash());
+    buf.extend_from_slice(&keccak256(&doughnut.issuer));
+    buf.extend_from_slice(&keccak256(&doughnut.holder));
+    buf.extend_from_slice(&pad_left_32(&[doughnut.fee_mode as u8]));
+    buf.extend_from_slice(&pad_left_32(&doughnut.expiry.to_be_bytes()));
+    buf.extend_from_slice(&pad_left_32(&doughnut.not_before.to_be_bytes()));
+    buf.extend_from_slice(&hash_toppings(&hashable_toppings(&doughnut.toppings)));
+    keccak256(&buf)
+}
+
+/// The EIP-191/712 digest (`keccak256(0x1901 || domainSeparator || hashStruct(message))`)
+/// a wallet signs for this doughnut under `chain_id`/`verifying_contract`.
+pub(crate) fn digest(
+    doughnut: &DoughnutV1,
+    chain_id: u64,
+    verifying_contract: &[u8; 20],
+) -> [u8; 32] {
+    let mut buf = Vec::with_capacity(2 + 32 + 32);
+    buf.extend_from_slice(&[0x19, 0x01]);
+    buf.extend_from_slice(&domain_separator(chain_id, verifying_contract));
+    buf.extend_from_slice(&hash_struct(doughnut));
+    keccak256(&buf)
+}
+
+/// Sign `digest` with `secret_key`, returning a 65-byte recoverable `r || s || v` signature.
+pub(crate) fn sign_digest(secret_key: &libsecp256k1::SecretKey, digest: &[u8; 32]) -> [u8; 65] {
+    let message = Message::parse(digest);
+    let (signature, recovery_id) = sign(&message, secret_key);
+    let mut out = [0u8; 65];
+    out[..64].copy_from_slice(&signature.serialize());
+    out[64] = recovery_id.serialize();
+    out
+}
+
+/// Verify that `doughnut.signature` is a valid EIP-712 signature over its own fields,
+/// recovering the signer and checking it matches `doughnut.issuer`. The `chainId`/
+/// `verifyingContract` the signature was produced against are read back from the
+/// reserved toppings `signEIP712` stashes them in.
+pub(crate) fn verify(doughnut: &DoughnutV1) -> bool {
+    let (chain_id, verifying_contract) = match domain_from_toppings(&doughnut.toppings) {
+        Some(domain) => domain,
+        None => return false,
+    };
+    let digest = digest(doughnut, chain_id, &verifying_contract);
+    let message = Message::parse(&digest);
+
+    if doughnut.signature.len() != 65 {
+        return false;
+    }
+    let signature = match Signature::parse_standard_slice(&doughnut.signature[..64]) {
+        Ok(signature) => signature,
+        Err(_) => return false,
+    };
+    let recovery_id = match RecoveryId::parse(doughnut.signature[64]) {
+        Ok(id) => id,
+        Err(_) => return false,
+    };
+
+    let recovered = match recover(&message, &signature, &recovery_id) {
+        Ok(public_key) => public_key,
+        Err(_) => return false,
+    };
+    recovered.serialize_compressed().as_slice() == doughnut.issuer.as_slice()
+}
+
+fn domain_from_toppings(toppings: &[(String, Vec<u8>)]) -> Option<(u64, [u8; 20])> {
+    let chain_id_bytes = toppings
+        .iter()
+        .find(|(key, _)| key == CHAIN_ID_TOPPING_KEY)?
+        .1
+        .clone();
+    let verifying_contract_bytes = toppings
+        .iter()
+        .find(|(key, _)| key == VERIFYING_CONTRACT_TOPPING_KEY)?
+        .1
+        .clone();
+    if chain_id_bytes.len() != 8 || verifying_contract_bytes.len() != 20 {
+        return None;
+    }
+    let mut chain_id_array = [0u8; 8];
+    chain_id_array.copy_from_slice(&chain_id_bytes);
+    let mut verifying_contract = [0u8; 20];
+    verifying_contract.copy_from_slice(&verifying_contract_bytes);
+    Some((u64::from_be_bytes(chain_id_array), verifying_contract))
+}
+
+/// Stash `chain_id`/`verifying_contract` as reserved toppings so `verify` can
+/// reconstruct the exact digest that was signed.
+pub(crate) fn with_domain_toppings(
+    mut toppings: Vec<(String, Vec<u8>)>,
+    chain_id: u64,
+    verifying_contract: &[u8; 20],
+) -> Vec<(String, Vec<u8>)> {
+    toppings
+        .retain(|(key, _)| key != CHAIN_ID_TOPPING_KEY && key != VERIFYING_CONTRACT_TOPPING_KEY);
+    toppings.push((
+        CHAIN_ID_TOPPING_KEY.to_string(),
+        chain_id.to_be_bytes().to_vec(),
+    ));
+    toppings.push((
+        VERIFYING_CONTRACT_TOPPING_KEY.to_string(),
+        verifying_contract.to_vec(),
+    ));
+    toppings
+}
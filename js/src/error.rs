@@ -0,0 +1,52 @@
+// Copyright 2023-2024 Futureverse Corporation Limited
+
+//! Structured, typed errors for the JS API surface, so callers can `switch` on a stable
+//! reason code instead of parsing console logs or matching on `JsValue::undefined()`.
+
+use alloc::string::String;
+use wasm_bindgen::prelude::*;
+
+/// Discriminated reason a doughnut operation failed, mirroring an `OpStatusCode`-style
+/// code set: stable, numeric, and meant to be matched on rather than parsed.
+#[wasm_bindgen(js_name = DoughnutErrorCode)]
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum DoughnutErrorCodeJS {
+    NotYetValid = 0,
+    Expired = 1,
+    WrongHolder = 2,
+    BadSignature = 3,
+    UnsupportedVersion = 4,
+    DecodeFailed = 5,
+    ToppingNotFound = 6,
+    InvalidInput = 7,
+    ChainTooDeep = 8,
+    BrokenChainLink = 9,
+    NotAttenuation = 10,
+}
+
+/// A structured error thrown to JS in place of a bare `undefined`. Carries the stable
+/// `code` to switch on, plus a human-readable `message` with the offending value(s).
+#[wasm_bindgen(js_name = DoughnutError)]
+pub struct DoughnutErrorJS {
+    code: DoughnutErrorCodeJS,
+    message: String,
+}
+
+impl DoughnutErrorJS {
+    pub(crate) fn new(code: DoughnutErrorCodeJS, message: String) -> Self {
+        Self { code, message }
+    }
+}
+
+#[wasm_bindgen(js_class = DoughnutError)]
+impl DoughnutErrorJS {
+    #[wasm_bindgen(getter)]
+    pub fn code(&self) -> DoughnutErrorCodeJS {
+        self.code
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn message(&self) -> String {
+        self.message.clone()
+    }
+}
@@ -0,0 +1,110 @@
+// Copyright 2023-2024 Futureverse Corporation Limited
+
+//! JWS-style compact (`header.payload.signature`) serialization for Doughnuts, so JS
+//! consumers can inspect and transport a doughnut with standard base64url/JSON tooling
+//! instead of raw SCALE bytes.
+
+use alloc::{
+    format,
+    string::{String, ToString},
+    vec::Vec,
+};
+use base64::{engine::general_purpose::URL_SAFE_NO_PAD, Engine as _};
+use serde::{Deserialize, Serialize};
+
+use crate::SignatureVersionJS;
+
+#[derive(Serialize, Deserialize)]
+pub(crate) struct Header {
+    pub alg: String,
+    pub typ: String,
+}
+
+/// The JSON-encoded doughnut fields carried by a JWS payload. Byte fields (`issuer`,
+/// `holder`, topping values) are `0x`-prefixed hex, matching how wallets and web3
+/// tooling already render addresses and calldata.
+#[derive(Serialize, Deserialize)]
+pub(crate) struct Payload {
+    pub issuer: String,
+    pub holder: String,
+    pub expiry: u32,
+    #[serde(rename = "notBefore")]
+    pub not_before: u32,
+    #[serde(rename = "feeMode")]
+    pub fee_mode: u8,
+    pub toppings: Vec<(String, String)>,
+}
+
+/// Map a `SignatureVersionJS` onto the JWS `alg` name that names it on the wire.
+pub(crate) fn alg_name(signature_version: u8) -> &'static str {
+    match signature_version {
+        v if v == SignatureVersionJS::Sr25519 as u8 => "Sr25519",
+        v if v == SignatureVersionJS::Ed25519 as u8 => "Ed25519",
+        v if v == SignatureVersionJS::ECDSA as u8 => "ES256K",
+        v if v == SignatureVersionJS::EIP191 as u8 => "EIP191",
+        v if v == SignatureVersionJS::EIP712 as u8 => "EIP712",
+        _ => "unknown",
+    }
+}
+
+/// Reverse of `alg_name`, for reconstructing a `JsHandle` from a parsed header.
+pub(crate) fn signature_version_from_alg(alg: &str) -> Option<u8> {
+    match alg {
+        "Sr25519" => Some(SignatureVersionJS::Sr25519 as u8),
+        "Ed25519" => Some(SignatureVersionJS::Ed25519 as u8),
+        "ES256K" => Some(SignatureVersionJS::ECDSA as u8),
+        "EIP191" => Some(SignatureVersionJS::EIP191 as u8),
+        "EIP712" => Some(SignatureVersionJS::EIP712 as u8),
+        _ => None,
+    }
+}
+
+pub(crate) fn encode(payload: &Payload, signature_version: u8, signature: &[u8]) -> String {
+    let header = Header {
+        alg: alg_name(signature_version).to_string(),
+        typ: "JWS".to_string(),
+    };
+    let header_json = serde_json::to_string(&header).unwrap_or_default();
+    let payload_json = serde_json::to_string(payload).unwrap_or_default();
+    format!(
+        "{}.{}.{}",
+        URL_SAFE_NO_PAD.encode(header_json),
+        URL_SAFE_NO_PAD.encode(payload_json),
+        URL_SAFE_NO_PAD.encode(signature),
+    )
+}
+
+pub(crate) fn decode(jws: &str) -> Option<(Header, Payload, Vec<u8>)> {
+    let mut parts = jws.split('.');
+    let header_b64 = parts.next()?;
+    let payload_b64 = parts.next()?;
+    let signature_b64 = parts.next()?;
+    if parts.next().is_some() {
+        return None;
+    }
+    let header: Header = serde_json::from_slice(&URL_SAFE_NO_PAD.decode(header_b64).ok()?).ok()?;
+    let payload: Payload =
+        serde_json::from_slice(&URL_SAFE_NO_PAD.decode(payload_b64).ok()?).ok()?;
+    let signature = URL_SAFE_NO_PAD.decode(signature_b64).ok()?;
+    Some((header, payload, signature))
+}
+
+pub(crate) fn to_hex(bytes: &[u8]) -> String {
+    let mut out = String::with_capacity(2 + bytes.len() * 2);
+    out.push_str("0x");
+    for byte in bytes {
+        out.push_str(&format!("{byte:02x}"));
+    }
+    out
+}
+
+pub(crate) fn from_hex(hex: &str) -> Option<Vec<u8>> {
+    let hex = hex.strip_prefix("0x").unwrap_or(hex);
+    if hex.len() % 2 != 0 {
+        return None;
+    }
+    (0..hex.len())
+        .step_by(2)
+        .map(|i| u8::from_str_radix(&hex[i..i + 2], 16).ok())
+        .collect()
+}
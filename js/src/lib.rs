@@ -5,7 +5,11 @@
 #![cfg_attr(not(feature = "std"), no_std)]
 extern crate alloc;
 
-use alloc::{format, string::ToString, vec::Vec};
+use alloc::{
+    format,
+    string::{String, ToString},
+    vec::Vec,
+};
 use codec::{Decode, Encode};
 use core::convert::TryInto;
 use doughnut_rs::{
@@ -14,7 +18,36 @@ use doughnut_rs::{
 };
 use wasm_bindgen::prelude::*;
 
+pub mod eip712;
+pub mod error;
+pub mod jws;
 pub mod topping;
+pub mod version;
+use error::{DoughnutErrorCodeJS, DoughnutErrorJS};
+use topping::is_attenuation_of;
+
+/// Reserved topping key under which `delegate` embeds the parent doughnut's encoded
+/// bytes, so a chain can be walked back to its root without a new wire format.
+const PROOF_TOPPING_KEY: &str = "_proof";
+
+/// Topping keys that carry delegation-chain or signing plumbing rather than a real
+/// granted permission. These are link-local: `delegate` strips them out of the
+/// toppings it copies forward (each link gets its own fresh `_proof`), and
+/// `verifyChain` excludes them from the attenuation check since the parent never
+/// granted them.
+const RESERVED_TOPPING_KEYS: &[&str] = &[
+    PROOF_TOPPING_KEY,
+    eip712::CHAIN_ID_TOPPING_KEY,
+    eip712::VERIFYING_CONTRACT_TOPPING_KEY,
+];
+
+fn is_reserved_topping_key(key: &str) -> bool {
+    RESERVED_TOPPING_KEYS.contains(&key)
+}
+
+/// Hard bound on delegation chain length, so walking an attacker-supplied `_proof`
+/// chain (e.g. a cyclic or self-referential one) can't loop or grow unboundedly.
+const MAX_CHAIN_DEPTH: usize = 16;
 
 #[wasm_bindgen]
 extern "C" {
@@ -46,6 +79,18 @@ fn from_slice_33(bytes: &[u8]) -> [u8; 33] {
     array
 }
 
+#[inline]
+fn from_slice_20(bytes: &[u8]) -> [u8; 20] {
+    let mut array = [0; 20];
+    if bytes.len() < 20 {
+        log("expected 20 byte array");
+        return array;
+    }
+    let bytes = &bytes[..array.len()]; // panics if not enough data
+    array.copy_from_slice(bytes);
+    array
+}
+
 /// A js handle for a rust versioned doughnut struct
 #[wasm_bindgen(js_name = Doughnut)]
 #[derive(Clone)]
@@ -113,21 +158,23 @@ impl JsHandle {
     pub fn signSr25519(&mut self, secret_key: &[u8]) -> Result<JsHandle, JsValue> {
         // only PayloadVersion::V0 supports Sr25519
         if self.payloadVersion() != PayloadVersion::V0 as u16 {
-            panic!("unsupported doughnut version and signing scheme");
+            return Err(unsupported_version_error(
+                "Sr25519 signing requires a V0 doughnut",
+            ));
         }
 
         let secret_key: [u8; 64] = secret_key
             .try_into()
-            .map_err(|_| JsValue::from_str("invalid secret key"))?;
+            .map_err(|_| invalid_input_error("invalid secret key"))?;
         if let Doughnut::V0(ref mut doughnut) = &mut self.0 {
-            let _signature = doughnut
+            doughnut
                 .sign_sr25519(&secret_key)
-                .map(|_| ())
-                // throws: 'undefined' in JS on error
-                .map_err(|_| JsValue::undefined())?;
+                .map_err(|_| bad_signature_error("Sr25519 signing failed"))?;
             return Ok(self.clone());
         }
-        panic!("unsupported doughnut version");
+        Err(unsupported_version_error(
+            "Sr25519 signing requires a V0 doughnut",
+        ))
     }
 
     #[allow(non_snake_case)]
@@ -135,21 +182,23 @@ impl JsHandle {
     pub fn signEd25519(&mut self, secret_key: &[u8]) -> Result<JsHandle, JsValue> {
         // only PayloadVersion::V0 supports Ed25519
         if self.payloadVersion() != PayloadVersion::V0 as u16 {
-            panic!("unsupported doughnut version and signing scheme");
+            return Err(unsupported_version_error(
+                "Ed25519 signing requires a V0 doughnut",
+            ));
         }
 
         let secret_key: [u8; 32] = secret_key
             .try_into()
-            .map_err(|_| JsValue::from_str("invalid secret key"))?;
+            .map_err(|_| invalid_input_error("invalid secret key"))?;
         if let Doughnut::V0(ref mut doughnut) = &mut self.0 {
-            let _signature = doughnut
+            doughnut
                 .sign_ed25519(&secret_key)
-                .map(|_| ())
-                // throws: 'undefined' in JS on error
-                .map_err(|_| JsValue::undefined())?;
+                .map_err(|_| bad_signature_error("Ed25519 signing failed"))?;
             return Ok(self.clone());
         }
-        panic!("unsupported doughnut version");
+        Err(unsupported_version_error(
+            "Ed25519 signing requires a V0 doughnut",
+        ))
     }
 
     #[allow(non_snake_case)]
@@ -157,21 +206,23 @@ impl JsHandle {
     pub fn signECDSA(&mut self, secret_key: &[u8]) -> Result<JsHandle, JsValue> {
         // only PayloadVersion::V1 supports ECDSA
         if self.payloadVersion() != PayloadVersion::V1 as u16 {
-            panic!("unsupported doughnut version and signing scheme");
+            return Err(unsupported_version_error(
+                "ECDSA signing requires a V1 doughnut",
+            ));
         }
 
         let secret_key: [u8; 32] = secret_key
             .try_into()
-            .map_err(|_| JsValue::from_str("invalid secret key"))?;
+            .map_err(|_| invalid_input_error("invalid secret key"))?;
         if let Doughnut::V1(ref mut doughnut) = &mut self.0 {
-            let _signature = doughnut
+            doughnut
                 .sign_ecdsa(&secret_key)
-                .map(|_| ())
-                // throws: 'undefined' in JS on error
-                .map_err(|_| JsValue::undefined())?;
+                .map_err(|_| bad_signature_error("ECDSA signing failed"))?;
             return Ok(self.clone());
         }
-        panic!("unsupported doughnut version");
+        Err(unsupported_version_error(
+            "ECDSA signing requires a V1 doughnut",
+        ))
     }
 
     #[allow(non_snake_case)]
@@ -179,21 +230,62 @@ impl JsHandle {
     pub fn signEIP191(&mut self, secret_key: &[u8]) -> Result<JsHandle, JsValue> {
         // only PayloadVersion::V1 supports EIP191
         if self.payloadVersion() != PayloadVersion::V1 as u16 {
-            panic!("unsupported doughnut version and signing scheme");
+            return Err(unsupported_version_error(
+                "EIP191 signing requires a V1 doughnut",
+            ));
         }
 
         let secret_key: [u8; 32] = secret_key
             .try_into()
-            .map_err(|_| JsValue::from_str("invalid secret key"))?;
+            .map_err(|_| invalid_input_error("invalid secret key"))?;
         if let Doughnut::V1(ref mut doughnut) = &mut self.0 {
-            let _signature = doughnut
+            doughnut
                 .sign_eip191(&secret_key)
-                .map(|_| ())
-                // throws: 'undefined' in JS on error
-                .map_err(|_| JsValue::undefined())?;
+                .map_err(|_| bad_signature_error("EIP191 signing failed"))?;
             return Ok(self.clone());
         }
-        panic!("unsupported doughnut version");
+        Err(unsupported_version_error(
+            "EIP191 signing requires a V1 doughnut",
+        ))
+    }
+
+    #[allow(non_snake_case)]
+    /// Sign and return an EIP-712 typed-data signature over a structured `Doughnut`
+    /// message (issuer, holder, feeMode, expiry, notBefore, toppings), so wallets like
+    /// MetaMask render the fields instead of the opaque hex blob `signEIP191` signs.
+    pub fn signEIP712(
+        &mut self,
+        secret_key: &[u8],
+        chainId: u64,
+        verifyingContract: &[u8],
+    ) -> Result<JsHandle, JsValue> {
+        // only PayloadVersion::V1 supports EIP712
+        if self.payloadVersion() != PayloadVersion::V1 as u16 {
+            return Err(unsupported_version_error(
+                "EIP712 signing requires a V1 doughnut",
+            ));
+        }
+
+        let secret_key_bytes: [u8; 32] = secret_key
+            .try_into()
+            .map_err(|_| invalid_input_error("invalid secret key"))?;
+        let secret_key = libsecp256k1::SecretKey::parse(&secret_key_bytes)
+            .map_err(|_| invalid_input_error("invalid secret key"))?;
+        let verifying_contract = from_slice_20(verifyingContract);
+        if let Doughnut::V1(ref mut doughnut) = &mut self.0 {
+            doughnut.toppings = eip712::with_domain_toppings(
+                doughnut.toppings.clone(),
+                chainId,
+                &verifying_contract,
+            );
+            let digest = eip712::digest(doughnut, chainId, &verifying_contract);
+            doughnut.signature = eip712::sign_digest(&secret_key, &digest);
+            doughnut.signature_version = SignatureVersionJS::EIP712 as u8;
+            return Ok(self.clone());
+        }
+        Err(unsupported_version_error(
+            "EIP712 signing requires a V1 doughnut",
+        ))
     }
 
     #[allow(non_snake_case)]
@@ -209,25 +301,30 @@ impl JsHandle {
                 if !(signature_version == SignatureVersionJS::Ed25519 as u8
                     || signature_version == SignatureVersionJS::Sr25519 as u8)
                 {
-                    panic!("unsupported doughnut version and signature version");
+                    return Err(unsupported_version_error(
+                        "unsupported doughnut version and signature version",
+                    ));
                 }
                 let signature: [u8; 64] = signature
                     .try_into()
-                    .map_err(|_| JsValue::from_str("invalid signature"))?;
+                    .map_err(|_| invalid_input_error("invalid signature"))?;
                 v0.signature_version = signature_version as u8;
                 v0.signature = signature.into();
                 return Ok(self.clone());
             }
             Doughnut::V1(v1) => {
-                // PayloadVersion::V1 supports SignatureVersion::ECDSA, SignatureVersion::EIP191
+                // PayloadVersion::V1 supports SignatureVersion::ECDSA, SignatureVersion::EIP191, SignatureVersion::EIP712
                 if !(signature_version == SignatureVersionJS::ECDSA as u8
-                    || signature_version == SignatureVersionJS::EIP191 as u8)
+                    || signature_version == SignatureVersionJS::EIP191 as u8
+                    || signature_version == SignatureVersionJS::EIP712 as u8)
                 {
-                    panic!("unsupported doughnut version and signature version");
+                    return Err(unsupported_version_error(
+                        "unsupported doughnut version and signature version",
+                    ));
                 }
                 let signature: [u8; 65] = signature
                     .try_into()
-                    .map_err(|_| JsValue::from_str("invalid signature"))?;
+                    .map_err(|_| invalid_input_error("invalid signature"))?;
                 v1.signature_version = signature_version as u8;
                 v1.signature = signature;
                 return Ok(self.clone());
@@ -334,40 +431,270 @@ impl JsHandle {
         }
     }
 
+    #[allow(non_snake_case)]
+    /// Returns `true` if this doughnut's payload version can be migrated onto `targetVersion`
+    pub fn canMigrate(&self, targetVersion: u16) -> bool {
+        version::can_migrate(self.payloadVersion(), targetVersion)
+    }
+
+    #[allow(non_snake_case)]
+    /// Convert this doughnut onto `targetVersion`. Today only the legacy `V0 -> V1` upgrade
+    /// is supported: since a V1 signature verifies against a secp256k1 public key rather
+    /// than a V0 account id, the caller must supply the `newIssuer`/`newHolder` secp256k1
+    /// keys the migrated doughnut will carry; toppings/expiry/notBefore carry across
+    /// unchanged, feeMode defaults to `Issuer`, and any existing signature is cleared since
+    /// the payload changed -- callers must re-sign deliberately with `newIssuer`'s key.
+    /// See `MigrationResult.notes` for exactly which fields required lossy/explicit handling.
+    pub fn migrate(
+        &self,
+        targetVersion: u16,
+        newIssuer: &[u8],
+        newHolder: &[u8],
+    ) -> Result<version::MigrationResultJS, JsValue> {
+        if !self.canMigrate(targetVersion) {
+            return Err(unsupported_version_error(
+                "unsupported doughnut version migration",
+            ));
+        }
+        match self.0 {
+            Doughnut::V0(ref v0) => {
+                let (v1, notes) = version::migrate_v0_to_v1(
+                    v0,
+                    from_slice_33(newIssuer),
+                    from_slice_33(newHolder),
+                );
+                Ok(version::MigrationResultJS::new(
+                    JsHandle(Doughnut::V1(v1)),
+                    notes,
+                ))
+            }
+            Doughnut::V1(_) => Err(unsupported_version_error(
+                "unsupported doughnut version migration",
+            )),
+        }
+    }
+
     /// Return the payload for topping, if it exists in the doughnut
-    /// This will throw "undefined" in JS if the topping is not found
+    /// This will throw a `DoughnutError` with code `ToppingNotFound` if the topping is not found
     pub fn topping(&self, topping: &str) -> Result<Vec<u8>, JsValue> {
         match self.0 {
             Doughnut::V0(ref doughnut) => {
                 return doughnut
                     .get_topping(topping)
                     .map(|d| Ok(d.to_vec()))
-                    .unwrap_or_else(|| Err(JsValue::undefined()))
+                    .unwrap_or_else(|| Err(topping_not_found_error(topping)))
             }
             Doughnut::V1(ref doughnut) => {
                 return doughnut
                     .get_topping(topping)
                     .map(|d| Ok(d.to_vec()))
-                    .unwrap_or_else(|| Err(JsValue::undefined()))
+                    .unwrap_or_else(|| Err(topping_not_found_error(topping)))
+            }
+        }
+    }
+
+    #[allow(non_snake_case)]
+    /// Mint a child doughnut re-delegating this doughnut to `childHolder`, becoming its
+    /// issuer. The child starts with this doughnut's toppings (narrow further with
+    /// `addTopping` before signing) and embeds this doughnut as its proof of delegation.
+    ///
+    /// The returned doughnut is unsigned; the holder key for *this* doughnut must sign
+    /// it before it is usable. Use `verifyChain` to validate the resulting chain.
+    pub fn delegate(&mut self, childHolder: &[u8], expiry: u32, notBefore: u32) -> JsHandle {
+        let proof = self.0.encode();
+        // only carry forward *granted* toppings -- reserved plumbing like the parent's
+        // own `_proof` is link-local and would otherwise leave the child with two
+        // `_proof` entries, breaking `chain()` for delegation depth >= 3
+        let granted_toppings: Vec<(String, Vec<u8>)> = toppings_of(&self.0)
+            .iter()
+            .filter(|(key, _)| !is_reserved_topping_key(key))
+            .cloned()
+            .collect();
+        let child = match self.0 {
+            Doughnut::V0(ref parent) => {
+                let mut child = DoughnutV0::default();
+                child.payload_version = PayloadVersion::V0 as u16;
+                child.issuer = parent.holder;
+                child.holder = from_slice_32(childHolder);
+                child.not_before = notBefore;
+                child.expiry = expiry;
+                child.toppings = granted_toppings;
+                Doughnut::V0(child)
+            }
+            Doughnut::V1(ref parent) => {
+                let mut child = DoughnutV1::default();
+                child.payload_version = PayloadVersion::V1 as u16;
+                child.issuer = parent.holder;
+                child.holder = from_slice_33(childHolder);
+                child.fee_mode = parent.fee_mode;
+                child.not_before = notBefore;
+                child.expiry = expiry;
+                child.toppings = granted_toppings;
+                Doughnut::V1(child)
+            }
+        };
+        let mut child = JsHandle(child);
+        child.addTopping(PROOF_TOPPING_KEY, &proof);
+        child
+    }
+
+    #[allow(non_snake_case)]
+    /// Walk the delegation chain embedded by `delegate` back to its root, verifying that
+    /// for every link:
+    /// 1) its issuer equals the previous link's holder,
+    /// 2) its toppings are an attenuation of the previous link's,
+    /// 3) its validity window nests inside the previous link's
+    ///    (`parent.notBefore <= child.notBefore <= child.expiry <= parent.expiry`),
+    /// 4) it carries a valid signature,
+    /// and that the root link's issuer is `who` and this (leaf) doughnut is usable at `when`.
+    ///
+    /// Throws a `DoughnutError` naming which link and which check failed, rather than
+    /// collapsing the reason into a bare `false`.
+    pub fn verifyChain(&self, who: &[u8], when: u32) -> Result<(), JsValue> {
+        let chain = self.chain()?;
+
+        for (index, link) in chain.iter().enumerate() {
+            if !link_verify(link) {
+                return Err(bad_signature_error(&format!(
+                    "chain link {index} has an invalid signature"
+                )));
+            }
+            if index == 0 {
+                if issuer_of(link).as_slice() != who {
+                    return Err(DoughnutErrorJS::new(
+                        DoughnutErrorCodeJS::WrongHolder,
+                        format!("chain root is not issued by {}", jws::to_hex(who)),
+                    )
+                    .into());
+                }
+            } else {
+                let parent = &chain[index - 1];
+                if issuer_of(link) != holder_of(parent) {
+                    return Err(DoughnutErrorJS::new(
+                        DoughnutErrorCodeJS::BrokenChainLink,
+                        format!(
+                            "chain link {index}'s issuer does not match the previous link's holder"
+                        ),
+                    )
+                    .into());
+                }
+                let link_toppings: Vec<(String, Vec<u8>)> = toppings_of(link)
+                    .iter()
+                    .filter(|(key, _)| !is_reserved_topping_key(key))
+                    .cloned()
+                    .collect();
+                let parent_toppings: Vec<(String, Vec<u8>)> = toppings_of(parent)
+                    .iter()
+                    .filter(|(key, _)| !is_reserved_topping_key(key))
+                    .cloned()
+                    .collect();
+                if !is_attenuation_of(&link_toppings, &parent_toppings) {
+                    return Err(DoughnutErrorJS::new(
+                        DoughnutErrorCodeJS::NotAttenuation,
+                        format!(
+                            "chain link {index}'s toppings are not an attenuation of the previous link's"
+                        ),
+                    )
+                    .into());
+                }
+                if not_before_of(parent) > not_before_of(link)
+                    || not_before_of(link) > expiry_of(link)
+                    || expiry_of(link) > expiry_of(parent)
+                {
+                    return Err(DoughnutErrorJS::new(
+                        DoughnutErrorCodeJS::InvalidInput,
+                        format!(
+                            "chain link {index}'s validity window does not nest inside the previous link's"
+                        ),
+                    )
+                    .into());
+                }
             }
         }
+
+        let leaf = chain.last().expect("chain is never empty");
+        if when < not_before_of(leaf) {
+            return Err(DoughnutErrorJS::new(
+                DoughnutErrorCodeJS::NotYetValid,
+                format!("chain leaf is not valid until {}", not_before_of(leaf)),
+            )
+            .into());
+        }
+        if when > expiry_of(leaf) {
+            return Err(DoughnutErrorJS::new(
+                DoughnutErrorCodeJS::Expired,
+                format!("chain leaf expired at {}", expiry_of(leaf)),
+            )
+            .into());
+        }
+        Ok(())
+    }
+
+    /// Collect the chain from root to `self`, following embedded `PROOF_TOPPING_KEY` proofs.
+    /// Bounded by `MAX_CHAIN_DEPTH` since `_proof` bytes come from the wire and a cyclic
+    /// or self-referential chain would otherwise loop/grow unboundedly.
+    fn chain(&self) -> Result<Vec<Doughnut>, JsValue> {
+        let mut chain = alloc::vec![self.0.clone()];
+        loop {
+            if chain.len() > MAX_CHAIN_DEPTH {
+                return Err(DoughnutErrorJS::new(
+                    DoughnutErrorCodeJS::ChainTooDeep,
+                    format!("delegation chain exceeds the maximum depth of {MAX_CHAIN_DEPTH}"),
+                )
+                .into());
+            }
+            let current = chain.first().expect("chain is never empty");
+            let proof = match toppings_of(current)
+                .iter()
+                .find(|(key, _)| key == PROOF_TOPPING_KEY)
+            {
+                Some((_, proof)) => proof.clone(),
+                None => break,
+            };
+            let parent = Doughnut::decode(&mut &proof[..]).map_err(|err| {
+                DoughnutErrorJS::new(
+                    DoughnutErrorCodeJS::DecodeFailed,
+                    format!("failed decoding chain proof: {err:?}"),
+                )
+            })?;
+            chain.insert(0, parent);
+        }
+        Ok(chain)
     }
 
     /// Verify the doughnut is:
     /// 1) issued to a public key (`who`)
     /// 2) usable at the current timestamp (`not_before` <= `now` <= `expiry`)
     /// 3) is correctly signed by the issuer
-    pub fn verify(&self, who: &[u8], when: u32) -> bool {
-        match self.0 {
-            Doughnut::V0(ref doughnut) => {
-                // TODO: Return errors
-                return doughnut.validate(who, when).is_ok() && doughnut.verify().is_ok();
-            }
-            Doughnut::V1(ref doughnut) => {
-                // TODO: Return errors
-                return doughnut.validate(who, when).is_ok() && doughnut.verify().is_ok();
-            }
+    ///
+    /// Throws a `DoughnutError` naming which of the three checks failed, rather than
+    /// collapsing the reason into a bare `false`.
+    pub fn verify(&self, who: &[u8], when: u32) -> Result<(), JsValue> {
+        if self.holder().as_slice() != who {
+            return Err(DoughnutErrorJS::new(
+                DoughnutErrorCodeJS::WrongHolder,
+                format!("doughnut is not held by {}", jws::to_hex(who)),
+            )
+            .into());
         }
+        if when < self.notBefore() {
+            return Err(DoughnutErrorJS::new(
+                DoughnutErrorCodeJS::NotYetValid,
+                format!("doughnut is not valid until {}", self.notBefore()),
+            )
+            .into());
+        }
+        if when > self.expiry() {
+            return Err(DoughnutErrorJS::new(
+                DoughnutErrorCodeJS::Expired,
+                format!("doughnut expired at {}", self.expiry()),
+            )
+            .into());
+        }
+        if !link_verify(&self.0) {
+            return Err(bad_signature_error("doughnut signature is invalid"));
+        }
+        Ok(())
     }
 
     /// Encode the doughnut into bytes
@@ -379,11 +706,143 @@ impl JsHandle {
     pub fn decode(input: &[u8]) -> Result<JsHandle, JsValue> {
         match Doughnut::decode(&mut &input[..]) {
             Ok(doughnut) => Ok(JsHandle(doughnut)),
-            Err(err) => {
-                log(&format!("failed decoding: {:?}", err));
-                Err(JsValue::undefined())
-            }
+            Err(err) => Err(DoughnutErrorJS::new(
+                DoughnutErrorCodeJS::DecodeFailed,
+                format!("failed decoding: {err:?}"),
+            )
+            .into()),
+        }
+    }
+
+    #[allow(non_snake_case)]
+    /// Serialize this doughnut as a compact JWS-style `header.payload.signature` string,
+    /// so JS callers can transport and inspect it with standard base64url/JSON tooling
+    /// instead of raw SCALE bytes. The canonical signing payload (`self.payload()`) is
+    /// unchanged, so a signature produced against it remains verifiable either way.
+    pub fn toJWS(&self) -> String {
+        let payload = jws::Payload {
+            issuer: jws::to_hex(&self.issuer()),
+            holder: jws::to_hex(&self.holder()),
+            expiry: self.expiry(),
+            not_before: self.notBefore(),
+            fee_mode: fee_mode_of(&self.0),
+            toppings: toppings_of(&self.0)
+                .iter()
+                .map(|(key, value)| (key.clone(), jws::to_hex(value)))
+                .collect(),
+        };
+        jws::encode(&payload, self.signatureVersion(), &self.signature())
+    }
+
+    #[allow(non_snake_case)]
+    /// Parse a compact JWS string produced by `toJWS` back into a `JsHandle`.
+    pub fn fromJWS(jws: &str) -> Result<JsHandle, JsValue> {
+        let (header, payload, signature) = self::jws::decode(jws).ok_or_else(|| {
+            DoughnutErrorJS::new(DoughnutErrorCodeJS::DecodeFailed, "invalid JWS".to_string())
+                .into()
+        })?;
+        let signature_version = self::jws::signature_version_from_alg(&header.alg)
+            .ok_or_else(|| unsupported_version_error("unsupported alg"))?;
+        let issuer = self::jws::from_hex(&payload.issuer)
+            .ok_or_else(|| invalid_input_error("invalid issuer"))?;
+        let holder = self::jws::from_hex(&payload.holder)
+            .ok_or_else(|| invalid_input_error("invalid holder"))?;
+
+        let doughnut_version = if signature_version == SignatureVersionJS::Sr25519 as u8
+            || signature_version == SignatureVersionJS::Ed25519 as u8
+        {
+            PayloadVersion::V0 as u16
+        } else {
+            PayloadVersion::V1 as u16
+        };
+
+        let mut handle = JsHandle::new(
+            doughnut_version,
+            &issuer,
+            &holder,
+            payload.fee_mode,
+            payload.expiry,
+            payload.not_before,
+        );
+        for (key, value) in payload.toppings {
+            let value = self::jws::from_hex(&value)
+                .ok_or_else(|| invalid_input_error("invalid topping value"))?;
+            handle.addTopping(&key, &value);
         }
+        handle.addSignature(&signature, signature_version)
+    }
+}
+
+fn unsupported_version_error(message: &str) -> JsValue {
+    DoughnutErrorJS::new(DoughnutErrorCodeJS::UnsupportedVersion, message.to_string()).into()
+}
+
+fn invalid_input_error(message: &str) -> JsValue {
+    DoughnutErrorJS::new(DoughnutErrorCodeJS::InvalidInput, message.to_string()).into()
+}
+
+fn bad_signature_error(message: &str) -> JsValue {
+    DoughnutErrorJS::new(DoughnutErrorCodeJS::BadSignature, message.to_string()).into()
+}
+
+fn topping_not_found_error(topping: &str) -> JsValue {
+    DoughnutErrorJS::new(
+        DoughnutErrorCodeJS::ToppingNotFound,
+        format!("topping '{topping}' not found"),
+    )
+    .into()
+}
+
+fn issuer_of(doughnut: &Doughnut) -> Vec<u8> {
+    match doughnut {
+        Doughnut::V0(d) => d.issuer().to_vec(),
+        Doughnut::V1(d) => d.issuer().to_vec(),
+    }
+}
+
+fn holder_of(doughnut: &Doughnut) -> Vec<u8> {
+    match doughnut {
+        Doughnut::V0(d) => d.holder().to_vec(),
+        Doughnut::V1(d) => d.holder().to_vec(),
+    }
+}
+
+fn expiry_of(doughnut: &Doughnut) -> u32 {
+    match doughnut {
+        Doughnut::V0(d) => d.expiry(),
+        Doughnut::V1(d) => d.expiry(),
+    }
+}
+
+fn not_before_of(doughnut: &Doughnut) -> u32 {
+    match doughnut {
+        Doughnut::V0(d) => d.not_before(),
+        Doughnut::V1(d) => d.not_before(),
+    }
+}
+
+fn fee_mode_of(doughnut: &Doughnut) -> u8 {
+    match doughnut {
+        // V0 predates fee modes; treat it as the default (issuer-pays) for JWS/migration purposes
+        Doughnut::V0(_) => FeeModeJS::ISSUER as u8,
+        Doughnut::V1(d) => d.fee_mode as u8,
+    }
+}
+
+fn toppings_of(doughnut: &Doughnut) -> &[(String, Vec<u8>)] {
+    match doughnut {
+        Doughnut::V0(d) => &d.toppings,
+        Doughnut::V1(d) => &d.toppings,
+    }
+}
+
+fn link_verify(doughnut: &Doughnut) -> bool {
+    match doughnut {
+        Doughnut::V0(d) => d.verify().is_ok(),
+        Doughnut::V1(d) if d.signature_version == SignatureVersionJS::EIP712 as u8 => {
+            eip712::verify(d)
+        }
+        Doughnut::V1(d) => d.verify().is_ok(),
     }
 }
 
@@ -401,6 +860,7 @@ pub enum SignatureVersionJS {
     Ed25519 = 1,
     ECDSA = 2,
     EIP191 = 3,
+    EIP712 = 4,
 }
 
 // enum to represent FeeMode
@@ -409,3 +869,134 @@ pub enum FeeModeJS {
     ISSUER = 0,
     HOLDER = 1,
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use libsecp256k1::{PublicKey, SecretKey};
+    use wasm_bindgen_test::*;
+
+    fn v1_keypair(scalar: u8) -> (SecretKey, [u8; 33]) {
+        let mut bytes = [0u8; 32];
+        bytes[31] = scalar;
+        let secret_key = SecretKey::parse(&bytes).expect("scalar is a valid secp256k1 key");
+        let public_key = PublicKey::from_secret_key(&secret_key);
+        (secret_key, public_key.serialize_compressed())
+    }
+
+    #[wasm_bindgen_test]
+    fn delegate_then_verify_chain_succeeds() {
+        let (root_sk, root_pub) = v1_keypair(1);
+        let (holder_sk, holder_pub) = v1_keypair(2);
+        let (_, grandholder_pub) = v1_keypair(3);
+
+        let mut root = JsHandle::new(PayloadVersion::V1 as u16, &root_pub, &holder_pub, 0, 100, 0);
+        root.addTopping("read", b"true");
+        let mut root = root
+            .signECDSA(&root_sk.serialize())
+            .expect("root signs with its own issuer key");
+
+        let mut child = root.delegate(&grandholder_pub, 90, 10);
+        let child = child
+            .signECDSA(&holder_sk.serialize())
+            .expect("holder re-delegates by signing as the child's issuer");
+
+        assert!(child.verifyChain(&root_pub, 50).is_ok());
+    }
+
+    #[wasm_bindgen_test]
+    fn verify_chain_rejects_broadened_toppings() {
+        let (root_sk, root_pub) = v1_keypair(1);
+        let (holder_sk, holder_pub) = v1_keypair(2);
+        let (_, grandholder_pub) = v1_keypair(3);
+
+        let mut root = JsHandle::new(PayloadVersion::V1 as u16, &root_pub, &holder_pub, 0, 100, 0);
+        root.addTopping("read", b"true");
+        let mut root = root.signECDSA(&root_sk.serialize()).expect("sign root");
+
+        let mut child = root.delegate(&grandholder_pub, 90, 10);
+        // broaden a topping the parent never granted this way -- should fail attenuation
+        child.addTopping("write", b"true");
+        let child = child.signECDSA(&holder_sk.serialize()).expect("sign child");
+
+        assert!(child.verifyChain(&root_pub, 50).is_err());
+    }
+
+    #[wasm_bindgen_test]
+    fn sign_eip712_then_verify_succeeds() {
+        let (issuer_sk, issuer_pub) = v1_keypair(4);
+        let (_, holder_pub) = v1_keypair(5);
+        let verifying_contract = [0x11u8; 20];
+
+        let mut doughnut = JsHandle::new(
+            PayloadVersion::V1 as u16,
+            &issuer_pub,
+            &holder_pub,
+            0,
+            100,
+            0,
+        );
+        doughnut.addTopping("read", b"true");
+        let doughnut = doughnut
+            .signEIP712(&issuer_sk.serialize(), 1, &verifying_contract)
+            .expect("sign with EIP-712");
+
+        assert!(doughnut.verify(&holder_pub, 50).is_ok());
+    }
+
+    #[wasm_bindgen_test]
+    fn to_jws_then_from_jws_round_trips() {
+        let (issuer_sk, issuer_pub) = v1_keypair(6);
+        let (_, holder_pub) = v1_keypair(7);
+
+        let mut doughnut = JsHandle::new(
+            PayloadVersion::V1 as u16,
+            &issuer_pub,
+            &holder_pub,
+            0,
+            100,
+            0,
+        );
+        doughnut.addTopping("read", b"true");
+        let mut doughnut = doughnut
+            .signECDSA(&issuer_sk.serialize())
+            .expect("sign doughnut");
+
+        let jws = doughnut.toJWS();
+        let mut parsed = JsHandle::fromJWS(&jws).expect("parse JWS");
+
+        assert_eq!(parsed.issuer(), doughnut.issuer());
+        assert_eq!(parsed.holder(), doughnut.holder());
+        assert_eq!(parsed.signature(), doughnut.signature());
+        assert_eq!(parsed.encode(), doughnut.encode());
+    }
+
+    #[wasm_bindgen_test]
+    fn migrate_v0_to_v1_round_trips() {
+        let issuer = [1u8; 32];
+        let holder = [2u8; 32];
+        let (new_issuer_sk, new_issuer_pub) = v1_keypair(8);
+        let (_, new_holder_pub) = v1_keypair(9);
+
+        let mut v0 = JsHandle::new(PayloadVersion::V0 as u16, &issuer, &holder, 0, 100, 10);
+        v0.addTopping("read", b"true");
+
+        let migrated = v0
+            .migrate(PayloadVersion::V1 as u16, &new_issuer_pub, &new_holder_pub)
+            .expect("V0 -> V1 migration is supported");
+        let mut v1 = migrated.doughnut();
+
+        assert_eq!(v1.payloadVersion(), PayloadVersion::V1 as u16);
+        assert_eq!(v1.issuer(), new_issuer_pub.to_vec());
+        assert_eq!(v1.holder(), new_holder_pub.to_vec());
+        assert_eq!(v1.expiry(), v0.expiry());
+        assert_eq!(v1.notBefore(), v0.notBefore());
+        assert_eq!(v1.topping("read").expect("topping carried across"), b"true");
+        assert!(!migrated.notes().is_empty());
+
+        let v1 = v1
+            .signECDSA(&new_issuer_sk.serialize())
+            .expect("migrated doughnut can be re-signed under its new issuer key");
+        assert!(v1.verify(&new_holder_pub, 50).is_ok());
+    }
+}
@@ -0,0 +1,31 @@
+// Copyright 2023-2024 Futureverse Corporation Limited
+
+//! Helpers for comparing toppings across a delegation chain
+
+use alloc::{string::String, vec::Vec};
+
+/// A single topping entry: permission domain key and its encoded value
+pub type Topping = (String, Vec<u8>);
+
+/// Returns `true` if `child` toppings are an attenuation of `parent` toppings.
+///
+/// Every topping key present in `child` must also be present in `parent`, with a value
+/// that is no broader than the parent's. A child cannot introduce a topping the parent
+/// didn't grant. Today this is exact-match-or-absent; per-topping narrowing (e.g. a
+/// numeric allowance shrinking) can be added inside `is_narrower_or_equal` without
+/// changing any call sites.
+pub(crate) fn is_attenuation_of(child: &[Topping], parent: &[Topping]) -> bool {
+    child.iter().all(|(key, value)| {
+        parent
+            .iter()
+            .find(|(parent_key, _)| parent_key == key)
+            .map_or(false, |(_, parent_value)| {
+                is_narrower_or_equal(key, value, parent_value)
+            })
+    })
+}
+
+/// Per-topping narrowing hook, defaulting to exact match.
+fn is_narrower_or_equal(_key: &str, child_value: &[u8], parent_value: &[u8]) -> bool {
+    child_value == parent_value
+}
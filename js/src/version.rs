@@ -0,0 +1,81 @@
+// Copyright 2023-2024 Futureverse Corporation Limited
+
+//! Version migration between doughnut payload versions, so holders of a legacy `V0`
+//! doughnut have one auditable conversion path onto `V1` rather than hand-rolling it.
+
+use alloc::{
+    string::{String, ToString},
+    vec::Vec,
+};
+use doughnut_rs::doughnut::{DoughnutV0, DoughnutV1};
+use wasm_bindgen::prelude::*;
+
+use crate::JsHandle;
+
+/// Returns `true` if a doughnut on `from_version` can be migrated onto `to_version`.
+/// Today only the legacy `V0 -> V1` upgrade is supported.
+pub(crate) fn can_migrate(from_version: u16, to_version: u16) -> bool {
+    from_version == 0 && to_version == 1
+}
+
+/// Convert a `V0` doughnut into an equivalent, unsigned `V1` doughnut under new
+/// `issuer`/`holder` secp256k1 keys. V1 signing verifies against a secp256k1 public
+/// key, which a V0 32-byte account id never is, so the V0 keys themselves cannot be
+/// carried across -- the caller must supply the secp256k1 keys it will hold (and
+/// re-sign with) the migrated doughnut under. Returns the migrated doughnut plus a
+/// human-readable note per field that required lossy or explicit handling, so the
+/// caller can review them before re-signing.
+pub(crate) fn migrate_v0_to_v1(
+    v0: &DoughnutV0,
+    issuer: [u8; 33],
+    holder: [u8; 33],
+) -> (DoughnutV1, Vec<String>) {
+    let mut v1 = DoughnutV1::default();
+    v1.payload_version = 1;
+    v1.issuer = issuer;
+    v1.holder = holder;
+    v1.fee_mode = 0.try_into().expect("0 is always a valid fee mode");
+    v1.expiry = v0.expiry;
+    v1.not_before = v0.not_before;
+    v1.toppings = v0.toppings.clone();
+    // v1.signature / v1.signature_version are left at their Default::default(), i.e. cleared
+
+    let notes = alloc::vec![
+        "issuer/holder: replaced with the caller-supplied V1 secp256k1 keys -- the V0 \
+         account ids cannot be reused, since a V1 signature verifies against a \
+         secp256k1 public key rather than a V0 account id"
+            .to_string(),
+        "feeMode: not present on V0, defaulted to FeeMode::Issuer".to_string(),
+        "signature: cleared -- the V1 payload differs from V0 and must be re-signed with \
+         the new issuer key"
+            .to_string(),
+    ];
+    (v1, notes)
+}
+
+/// The outcome of `JsHandle::migrate`: the converted doughnut plus which fields required
+/// lossy or explicit handling, so callers can review them before re-signing.
+#[wasm_bindgen(js_name = MigrationResult)]
+pub struct MigrationResultJS {
+    doughnut: JsHandle,
+    notes: Vec<String>,
+}
+
+impl MigrationResultJS {
+    pub(crate) fn new(doughnut: JsHandle, notes: Vec<String>) -> Self {
+        Self { doughnut, notes }
+    }
+}
+
+#[wasm_bindgen(js_class = MigrationResult)]
+impl MigrationResultJS {
+    #[wasm_bindgen(getter)]
+    pub fn doughnut(&self) -> JsHandle {
+        self.doughnut.clone()
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn notes(&self) -> Vec<String> {
+        self.notes.clone()
+    }
+}